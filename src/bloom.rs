@@ -0,0 +1,175 @@
+// =========================================================
+//   BLOOM FILTER
+//   ---------------------------------------------------------
+//   Probabilistic set-membership over HLS-1024 digests, for PQ
+//   systems that want a cheap "have I seen this commitment/key?"
+//   check. Recasts the shift-bloom / bloom-part technique used
+//   over fixed hashes in Ethereum-style util code onto this
+//   crate's 1024-bit digest.
+// =========================================================
+
+use crate::Hls1024Hash;
+
+pub const DefaultBits: usize = 2048;
+pub const DefaultHashes: usize = 3;
+
+/// Number of bits needed to index `m` positions, i.e. `ceil(log2(m))`.
+fn window_bits(m: usize) -> usize {
+    let mut bits = 0;
+    while (1usize << bits) < m {
+        bits += 1;
+    }
+    bits.max(1)
+}
+
+/// Read `num_bits` starting at bit offset `bit_offset` out of `data`
+/// (big-endian, MSB-first), as an unsigned integer.
+fn read_bits(data: &[u8], bit_offset: usize, num_bits: usize) -> usize {
+    let mut value: usize = 0;
+    for i in 0..num_bits {
+        let bit_pos = bit_offset + i;
+        let byte_idx = bit_pos / 8;
+        let bit = if byte_idx < data.len() {
+            (data[byte_idx] >> (7 - bit_pos % 8)) & 1
+        } else {
+            0
+        };
+        value = (value << 1) | bit as usize;
+    }
+    value
+}
+
+/// A Bloom filter over HLS-1024 digests: `m`-bit array, `k` hash
+/// positions per item, each position a successive window of
+/// `ceil(log2(m))` bits taken off the leading digest bytes.
+pub struct Bloom {
+    bits: Vec<u64>,
+    m: usize,
+    k: usize,
+}
+
+impl Bloom {
+    pub fn new() -> Self {
+        Self::with_params(DefaultBits, DefaultHashes)
+    }
+
+    /// `m` need not be a power of two, but `indices()` maps each
+    /// `ceil(log2(m))`-bit window into `0..m` via `% m`, which is
+    /// only a uniform mapping when `m` is itself a power of two
+    /// (true for `DefaultBits`). A non-power-of-two `m` is still
+    /// correct but biases lower indices slightly more likely.
+    ///
+    /// Panics if `m == 0` (an empty bit array can't be indexed),
+    /// rather than deferring that panic to the first `insert`/
+    /// `contains` call.
+    pub fn with_params(m: usize, k: usize) -> Self {
+        assert!(m > 0, "Bloom filter must have at least 1 bit (m > 0)");
+        Bloom {
+            bits: vec![0u64; m.div_ceil(64)],
+            m,
+            k,
+        }
+    }
+
+    fn indices(&self, item: &[u8]) -> Vec<usize> {
+        let digest = Hls1024Hash(item);
+        let bytes = digest.as_bytes();
+        let wbits = window_bits(self.m);
+        (0..self.k)
+            .map(|i| read_bits(bytes, i * wbits, wbits) % self.m)
+            .collect()
+    }
+
+    fn set_bit(&mut self, idx: usize) {
+        self.bits[idx / 64] |= 1u64 << (idx % 64);
+    }
+
+    fn get_bit(&self, idx: usize) -> bool {
+        (self.bits[idx / 64] >> (idx % 64)) & 1 == 1
+    }
+
+    pub fn insert(&mut self, item: &[u8]) {
+        for idx in self.indices(item) {
+            self.set_bit(idx);
+        }
+    }
+
+    pub fn contains(&self, item: &[u8]) -> bool {
+        self.indices(item).into_iter().all(|idx| self.get_bit(idx))
+    }
+
+    /// Bitwise-OR this filter with `other`, shift-bloom style.
+    /// `None` if the filters have different `m`/`k` parameters.
+    pub fn union(&self, other: &Bloom) -> Option<Bloom> {
+        if self.m != other.m || self.k != other.k {
+            return None;
+        }
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .map(|(a, b)| a | b)
+            .collect();
+        Some(Bloom {
+            bits,
+            m: self.m,
+            k: self.k,
+        })
+    }
+
+    fn popcount(&self) -> u32 {
+        self.bits.iter().map(|w| w.count_ones()).sum()
+    }
+
+    /// Estimated false-positive rate `(n/m)^k` from the current
+    /// fraction of bits set.
+    pub fn false_positive_rate(&self) -> f64 {
+        let n = self.popcount() as f64;
+        let m = self.m as f64;
+        (n / m).powi(self.k as i32)
+    }
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_then_contains() {
+        let mut bloom = Bloom::new();
+        bloom.insert(b"commitment-a");
+        assert!(bloom.contains(b"commitment-a"));
+        assert!(!bloom.contains(b"commitment-never-inserted"));
+    }
+
+    #[test]
+    fn union_preserves_membership() {
+        let mut a = Bloom::new();
+        a.insert(b"from-a");
+        let mut b = Bloom::new();
+        b.insert(b"from-b");
+
+        let merged = a.union(&b).expect("same-shaped filters union");
+        assert!(merged.contains(b"from-a"));
+        assert!(merged.contains(b"from-b"));
+    }
+
+    #[test]
+    fn union_rejects_mismatched_params() {
+        let a = Bloom::with_params(2048, 3);
+        let b = Bloom::with_params(1024, 3);
+        assert!(a.union(&b).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "m > 0")]
+    fn zero_bits_panics_at_construction() {
+        Bloom::with_params(0, 3);
+    }
+}
@@ -0,0 +1,75 @@
+// =========================================================
+//   DIGEST TYPE
+//   ---------------------------------------------------------
+//   A fixed-size, constant-time-comparable handle for HLS-1024
+//   output, in place of a bare `Vec<u8>` whose length isn't
+//   enforced and whose equality isn't timing-safe. Mirrors the
+//   `FixedHash`-style pattern (fixed array backing, `random()`,
+//   byte views) used for hash types in established crates.
+// =========================================================
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use subtle::ConstantTimeEq;
+
+use crate::OutputBitLength;
+
+pub const DigestBytes: usize = OutputBitLength / 8;
+
+/// A 1024-bit HLS-1024 digest.
+#[derive(Clone, Copy, Debug)]
+pub struct Digest1024([u8; DigestBytes]);
+
+impl Digest1024 {
+    pub fn from_array(bytes: [u8; DigestBytes]) -> Self {
+        Digest1024(bytes)
+    }
+
+    /// Parse a digest from a byte slice; `None` if the length is wrong.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != DigestBytes {
+            return None;
+        }
+        let mut arr = [0u8; DigestBytes];
+        arr.copy_from_slice(bytes);
+        Some(Digest1024(arr))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn to_hex(self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Parse a digest from its hex encoding; `None` if malformed or
+    /// the wrong length.
+    pub fn from_hex(s: &str) -> Option<Self> {
+        let decoded = hex::decode(s).ok()?;
+        Self::from_bytes(&decoded)
+    }
+
+    /// Build a digest-shaped random value from an OS RNG, for use
+    /// as a test fixture (e.g. a fake "expected tag" to compare
+    /// against).
+    pub fn random() -> Self {
+        let mut bytes = [0u8; DigestBytes];
+        OsRng.fill_bytes(&mut bytes);
+        Digest1024(bytes)
+    }
+
+    /// Constant-time equality check, so comparing a computed digest
+    /// against an expected tag doesn't leak timing information.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl PartialEq for Digest1024 {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for Digest1024 {}
@@ -0,0 +1,89 @@
+// =========================================================
+//   KEYED MAC
+//   ---------------------------------------------------------
+//   `AbsorbMessageBlock` folds message words into the public
+//   `InitializeState()`, so a naive key-prefix construction
+//   over `Hls1024Hash` is vulnerable to the boundary-ambiguity
+//   and length-extension abuse classic Merkle-Damgard hashes
+//   exhibit. `Hls1024Mac` instead starts from a secret state
+//   derived from the key via domain separation, and binds the
+//   key length into finalization.
+// =========================================================
+
+use num_bigint_dig::BigUint;
+
+use crate::digest::Digest1024;
+use crate::{
+    AbsorbMessageBlock, ExtractDigest, FinalizeState, PerformRound, PrimeModulusValue,
+    ShakeInts, SplitIntoBlocks, BlockBytes, RoundCount, SeedString, StateSize,
+};
+
+fn DeriveMacState(key: &[u8]) -> Vec<BigUint> {
+    let mut seed = Vec::new();
+    seed.extend_from_slice(SeedString);
+    seed.extend_from_slice(b"::mac::");
+    seed.extend_from_slice(key);
+
+    let prime = PrimeModulusValue();
+    ShakeInts(&seed, StateSize, None)
+        .into_iter()
+        .map(|x| x % &prime)
+        .collect()
+}
+
+/// Keyed MAC over the HLS-1024 sponge. Unlike `Hls1024Hash`, the
+/// starting state is secret-derived from `key`, so observing
+/// `finalize()` output does not hand an attacker a state they
+/// can resume hashing from.
+pub struct Hls1024Mac {
+    state: Vec<BigUint>,
+    buffer: Vec<u8>,
+    key_len: u64,
+}
+
+impl Hls1024Mac {
+    pub fn new(key: &[u8]) -> Self {
+        Hls1024Mac {
+            state: DeriveMacState(key),
+            buffer: Vec::new(),
+            key_len: key.len() as u64,
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        self.state = AbsorbMessageBlock(&self.state, block);
+        for _ in 0..RoundCount {
+            self.state = PerformRound(&self.state);
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        while self.buffer.len() >= BlockBytes {
+            let block: Vec<u8> = self.buffer.drain(..BlockBytes).collect();
+            self.absorb_block(&block);
+        }
+    }
+
+    /// Finalize the tag. The key length is appended as an 8-byte
+    /// big-endian suffix before padding, so `(key, message)` pairs
+    /// can't be confused across a boundary (e.g. a short key plus
+    /// message-with-suffix colliding with a longer key plus message).
+    pub fn finalize(mut self) -> Digest1024 {
+        let mut tail = std::mem::take(&mut self.buffer);
+        tail.extend_from_slice(&self.key_len.to_be_bytes());
+
+        for blk in SplitIntoBlocks(&tail) {
+            self.absorb_block(&blk);
+        }
+
+        self.state = FinalizeState(&self.state);
+        Digest1024::from_bytes(&ExtractDigest(&self.state)).unwrap()
+    }
+}
+
+pub fn Hls1024MacTag(key: &[u8], message: &[u8]) -> Digest1024 {
+    let mut mac = Hls1024Mac::new(key);
+    mac.update(message);
+    mac.finalize()
+}
@@ -27,7 +27,19 @@ use num_traits::{One, Zero};
 use sha3::{Shake128, Shake256};
 use sha3::digest::{Update, ExtendableOutput, XofReader};
 
-use hex;
+mod streaming;
+use streaming::Hls1024;
+
+mod pow;
+
+mod mac;
+
+mod digest;
+use digest::Digest1024;
+
+mod bloom;
+
+mod field;
 
 // =========================================================
 //   GLOBAL PARAMETERS
@@ -56,8 +68,8 @@ pub fn InitializeParameters() {
             "E485B576625E7EC6F44C42E9A63A36210000000000090563"
         );
         let prime = BigUint::parse_bytes(hex_str.as_bytes(), 16).unwrap();
-        let bits = prime.bits() as usize;
-        let bytes = (bits + 7) / 8;
+        let bits = prime.bits();
+        let bytes = bits.div_ceil(8);
 
         PRIME_MODULUS.set(prime).ok();
         WORD_BITS.set(bits).ok();
@@ -65,6 +77,11 @@ pub fn InitializeParameters() {
     }
 }
 
+pub fn PrimeModulusValue() -> BigUint {
+    InitializeParameters();
+    PRIME_MODULUS.get().unwrap().clone()
+}
+
 pub fn WordBitsValue() -> usize {
     *WORD_BITS.get_or_init(|| {
         InitializeParameters();
@@ -133,28 +150,140 @@ pub fn Rol(x: &BigUint, r: usize, bits: usize) -> BigUint {
     ((x << r) | (x >> (bits - r))) & mask
 }
 
-pub fn AbsorbMessageBlock(state: &Vec<BigUint>, block: &[u8]) -> Vec<BigUint> {
-    let mut s = state.clone();
+fn ReduceModPrime(x: &BigUint) -> BigUint {
+    field::reduce(x)
+}
+
+fn PowSmall(x: &BigUint) -> (BigUint, BigUint) {
+    field::pow3_pow5(x)
+}
+
+// =========================================================
+//   LEGACY ROUND TRANSFORMS (SELF-TEST CROSS-CHECK ONLY)
+//   ---------------------------------------------------------
+//   The original `%`/`modpow` round transforms, kept around so
+//   RunFieldSelfTest (`--selftest`) can run the same message
+//   through both pipelines and assert on identical digests, and
+//   so RunFieldBenchmark (`--bench-field`) can time them against
+//   the Barrett path. Not part of the public API.
+// =========================================================
+
+fn LegacyReduceModPrime(x: &BigUint) -> BigUint {
+    InitializeParameters();
+    x % PRIME_MODULUS.get().unwrap()
+}
+
+fn LegacyPowSmall(x: &BigUint) -> (BigUint, BigUint) {
+    let prime = PRIME_MODULUS.get().unwrap();
+    (
+        x.modpow(&BigUint::from(3u32), prime),
+        x.modpow(&BigUint::from(5u32), prime),
+    )
+}
+
+fn LegacyAbsorbMessageBlock(state: &[BigUint], block: &[u8]) -> Vec<BigUint> {
+    let mut s = state.to_vec();
     let wb = WordBitsValue();
     let word_bytes = 8;
 
     let mut padded = block.to_vec();
-    if padded.len() % word_bytes != 0 {
+    if !padded.len().is_multiple_of(word_bytes) {
         let pad_len = word_bytes - (padded.len() % word_bytes);
         padded.extend(vec![0u8; pad_len]);
     }
 
     let words: Vec<BigUint> = padded
         .chunks(word_bytes)
-        .map(|chunk| BigUint::from_bytes_be(chunk))
+        .map(BigUint::from_bytes_be)
         .collect();
 
-    let prime = PRIME_MODULUS.get().unwrap();
+    for (i, w) in words.iter().enumerate() {
+        let idx = i % s.len();
+        let curr = s[idx].clone();
+        s[idx] = LegacyReduceModPrime(&(curr + w));
+
+        let next_idx = (idx + 1) % s.len();
+        let next_curr = s[next_idx].clone();
+        let shift = w >> 16usize;
+        let mask = (BigUint::one() << wb) - BigUint::one();
+        let updated_next = next_curr ^ (&shift & &mask);
+        s[next_idx] = updated_next;
+    }
+
+    s
+}
+
+fn LegacyApplyLinearDiffusion(state: &[BigUint]) -> Vec<BigUint> {
+    let n = state.len();
+    let wb = WordBitsValue();
+    let mut out = vec![BigUint::zero(); n];
+
+    for i in 0..n {
+        let a = &state[i];
+        let b = &state[(i + 1) % n];
+        let c = &state[(i + 7) % n];
+        let mix = LegacyReduceModPrime(&(a + &(b ^ &(c >> 3usize))));
+        out[i] = Rol(&mix, (i * 3) % wb, wb);
+    }
+    out
+}
+
+fn LegacyApplyNonLinearConfusion(state: &[BigUint]) -> Vec<BigUint> {
+    state
+        .iter()
+        .map(|x| {
+            let (x3, x5) = LegacyPowSmall(x);
+            LegacyReduceModPrime(&(x3 + x5 + BigUint::from(17u32)))
+        })
+        .collect()
+}
+
+fn LegacyPerformRound(state: &[BigUint]) -> Vec<BigUint> {
+    let s = LegacyApplyLinearDiffusion(state);
+    LegacyApplyNonLinearConfusion(&s)
+}
+
+fn LegacyFinalizeState(state: &[BigUint]) -> Vec<BigUint> {
+    let mut s = state.to_vec();
+    for _ in 0..4 {
+        s = LegacyApplyLinearDiffusion(&s);
+        s = LegacyApplyNonLinearConfusion(&s);
+    }
+    s
+}
+
+fn LegacyHls1024Hash(message: &[u8]) -> Digest1024 {
+    let mut state = InitializeState();
+    for blk in SplitIntoBlocks(message) {
+        state = LegacyAbsorbMessageBlock(&state, &blk);
+        for _ in 0..RoundCount {
+            state = LegacyPerformRound(&state);
+        }
+    }
+    state = LegacyFinalizeState(&state);
+    Digest1024::from_bytes(&ExtractDigest(&state)).unwrap()
+}
+
+pub fn AbsorbMessageBlock(state: &[BigUint], block: &[u8]) -> Vec<BigUint> {
+    let mut s = state.to_vec();
+    let wb = WordBitsValue();
+    let word_bytes = 8;
+
+    let mut padded = block.to_vec();
+    if !padded.len().is_multiple_of(word_bytes) {
+        let pad_len = word_bytes - (padded.len() % word_bytes);
+        padded.extend(vec![0u8; pad_len]);
+    }
+
+    let words: Vec<BigUint> = padded
+        .chunks(word_bytes)
+        .map(BigUint::from_bytes_be)
+        .collect();
 
     for (i, w) in words.iter().enumerate() {
         let idx = i % s.len();
         let curr = s[idx].clone();
-        s[idx] = (curr + w) % prime;
+        s[idx] = ReduceModPrime(&(curr + w));
 
         let next_idx = (idx + 1) % s.len();
         let next_curr = s[next_idx].clone();
@@ -167,35 +296,32 @@ pub fn AbsorbMessageBlock(state: &Vec<BigUint>, block: &[u8]) -> Vec<BigUint> {
     s
 }
 
-pub fn ApplyLinearDiffusion(state: &Vec<BigUint>) -> Vec<BigUint> {
+pub fn ApplyLinearDiffusion(state: &[BigUint]) -> Vec<BigUint> {
     let n = state.len();
     let wb = WordBitsValue();
-    let prime = PRIME_MODULUS.get().unwrap();
     let mut out = vec![BigUint::zero(); n];
 
     for i in 0..n {
         let a = &state[i];
         let b = &state[(i + 1) % n];
         let c = &state[(i + 7) % n];
-        let mix = (a + &(b ^ &(c >> 3usize))) % prime;
+        let mix = ReduceModPrime(&(a + &(b ^ &(c >> 3usize))));
         out[i] = Rol(&mix, (i * 3) % wb, wb);
     }
     out
 }
 
-pub fn ApplyNonLinearConfusion(state: &Vec<BigUint>) -> Vec<BigUint> {
-    let prime = PRIME_MODULUS.get().unwrap();
+pub fn ApplyNonLinearConfusion(state: &[BigUint]) -> Vec<BigUint> {
     state
         .iter()
         .map(|x| {
-            let x3 = x.modpow(&BigUint::from(3u32), prime);
-            let x5 = x.modpow(&BigUint::from(5u32), prime);
-            (x3 + x5 + BigUint::from(17u32)) % prime
+            let (x3, x5) = PowSmall(x);
+            ReduceModPrime(&(x3 + x5 + BigUint::from(17u32)))
         })
         .collect()
 }
 
-pub fn PerformRound(state: &Vec<BigUint>) -> Vec<BigUint> {
+pub fn PerformRound(state: &[BigUint]) -> Vec<BigUint> {
     let s = ApplyLinearDiffusion(state);
     ApplyNonLinearConfusion(&s)
 }
@@ -204,8 +330,8 @@ pub fn PerformRound(state: &Vec<BigUint>) -> Vec<BigUint> {
 //   FINALIZATION
 // =========================================================
 
-pub fn FinalizeState(state: &Vec<BigUint>) -> Vec<BigUint> {
-    let mut s = state.clone();
+pub fn FinalizeState(state: &[BigUint]) -> Vec<BigUint> {
+    let mut s = state.to_vec();
     for _ in 0..4 {
         s = ApplyLinearDiffusion(&s);
         s = ApplyNonLinearConfusion(&s);
@@ -213,7 +339,7 @@ pub fn FinalizeState(state: &Vec<BigUint>) -> Vec<BigUint> {
     s
 }
 
-pub fn ExtractDigest(state: &Vec<BigUint>) -> Vec<u8> {
+pub fn ExtractReader(state: &Vec<BigUint>) -> <Shake256 as ExtendableOutput>::Reader {
     InitializeParameters();
 
     let mut hasher = Shake256::default();
@@ -232,7 +358,11 @@ pub fn ExtractDigest(state: &Vec<BigUint>) -> Vec<u8> {
         hasher.update(&full_bytes);
     }
 
-    let mut reader = hasher.finalize_xof();
+    hasher.finalize_xof()
+}
+
+pub fn ExtractDigest(state: &Vec<BigUint>) -> Vec<u8> {
+    let mut reader = ExtractReader(state);
     let mut out = vec![0u8; OutputBitLength / 8];
     XofReader::read(&mut reader, &mut out);
     out
@@ -255,18 +385,10 @@ pub fn SplitIntoBlocks(message: &[u8]) -> Vec<Vec<u8>> {
     padded.chunks(rate).map(|chunk| chunk.to_vec()).collect()
 }
 
-pub fn Hls1024Hash(message: &[u8]) -> Vec<u8> {
-    let mut state = InitializeState();
-
-    for blk in SplitIntoBlocks(message) {
-        state = AbsorbMessageBlock(&state, &blk);
-        for _ in 0..RoundCount {
-            state = PerformRound(&state);
-        }
-    }
-
-    state = FinalizeState(&state);
-    ExtractDigest(&state)
+pub fn Hls1024Hash(message: &[u8]) -> Digest1024 {
+    let mut hasher = streaming::Hls1024::new();
+    hasher.update(message);
+    hasher.finalize()
 }
 
 // =========================================================
@@ -283,6 +405,157 @@ fn RunSelfTest() {
     } else {
         println!("PASS: Deterministic");
     }
+
+    RunMacSelfTest();
+    RunFieldSelfTest();
+    RunBloomSelfTest();
+}
+
+fn RunBloomSelfTest() {
+    println!("Running HLS-1024 Bloom filter self-test...");
+
+    let mut seen = bloom::Bloom::new();
+    let members: [&[u8]; 3] = [b"commitment-a", b"commitment-b", b"commitment-c"];
+    let absent: &[u8] = b"commitment-never-inserted";
+
+    for item in members {
+        seen.insert(item);
+    }
+
+    let all_present = members.iter().all(|item| seen.contains(item));
+    if !all_present {
+        println!("FAIL: inserted member reported absent");
+        return;
+    }
+
+    let mut other = bloom::Bloom::new();
+    other.insert(absent);
+    let merged = match seen.union(&other) {
+        Some(m) => m,
+        None => {
+            println!("FAIL: union of same-shaped filters was rejected");
+            return;
+        }
+    };
+
+    if !merged.contains(absent) || !members.iter().all(|item| merged.contains(item)) {
+        println!("FAIL: union filter lost a member");
+        return;
+    }
+
+    println!(
+        "PASS: insert/contains/union consistent (estimated FP rate {:.4})",
+        merged.false_positive_rate()
+    );
+}
+
+fn RunFieldSelfTest() {
+    println!("Running HLS-1024 field arithmetic self-test...");
+
+    // Cross-check Barrett reduction / pow3_pow5 against the naive
+    // `%`/`modpow` path. `InitializeState()` samples alone only hit
+    // `reduce`'s trivial early-return branch (they're already
+    // `< prime`), so `field::self_check` additionally squares each
+    // sample to drive real values through the Barrett q/r arithmetic.
+    let samples = InitializeState();
+    let arithmetic_ok = field::self_check(&samples);
+    println!(
+        "{}: Barrett reduction vs naive modular arithmetic",
+        if arithmetic_ok { "PASS" } else { "FAIL" }
+    );
+
+    // The actual "identical digests" proof: hash the same message
+    // once through the Barrett-based pipeline and once through the
+    // Legacy* pipeline kept above purely for this comparison.
+    let msg = b"field-arithmetic-cross-check";
+    let barrett_digest = Hls1024Hash(msg);
+    let legacy_digest = LegacyHls1024Hash(msg);
+    let digests_match = barrett_digest == legacy_digest;
+    println!(
+        "{}: Barrett and legacy pipelines produce identical digests",
+        if digests_match { "PASS" } else { "FAIL" }
+    );
+}
+
+/// Compare Barrett-reduction round throughput against the
+/// original heap-allocated `BigUint` `%`/`modpow` path it
+/// replaces, over the same starting state.
+fn RunFieldBenchmark() {
+    use std::time::Instant;
+
+    const ITERATIONS: usize = 200;
+
+    let state = InitializeState();
+
+    let start = Instant::now();
+    let mut s = state.clone();
+    for _ in 0..ITERATIONS {
+        s = PerformRound(&s);
+    }
+    let barrett_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let mut s = state;
+    for _ in 0..ITERATIONS {
+        s = LegacyPerformRound(&s);
+    }
+    let legacy_elapsed = start.elapsed();
+
+    println!(
+        "barrett: {:?} ({} rounds) | legacy: {:?} ({} rounds)",
+        barrett_elapsed, ITERATIONS, legacy_elapsed, ITERATIONS
+    );
+}
+
+/// Model the actual length-extension move: an attacker who only has
+/// a leaked `tag` (not `key`) treats it as if it were a resumable
+/// internal sponge state, expands it out to `StateSize` words, and
+/// continues absorbing their own `suffix` from there — exactly how
+/// a length-extension forgery resumes a Merkle-Damgard hash whose
+/// output doubles as its raw chaining value.
+///
+/// Against `Hls1024Mac` this can't work even in principle: the real
+/// internal state is 512 multi-hundred-bit words derived from the
+/// secret key, while `tag` is only 1024 bits, so the attacker's
+/// "resumed state" is never the real one, key or no key.
+fn ForgeByStateReconstruction(tag: &Digest1024, suffix: &[u8]) -> Digest1024 {
+    let prime = PrimeModulusValue();
+    let mut state: Vec<BigUint> = ShakeInts(tag.as_bytes(), StateSize, None)
+        .into_iter()
+        .map(|x| x % &prime)
+        .collect();
+
+    for blk in SplitIntoBlocks(suffix) {
+        state = AbsorbMessageBlock(&state, &blk);
+        for _ in 0..RoundCount {
+            state = PerformRound(&state);
+        }
+    }
+    state = FinalizeState(&state);
+    Digest1024::from_bytes(&ExtractDigest(&state)).unwrap()
+}
+
+fn RunMacSelfTest() {
+    println!("Running HLS-1024 MAC self-test...");
+
+    let key = b"super-secret-mac-key";
+    let message = b"transfer:100:to:alice";
+    let suffix = b":admin:true";
+
+    // The attacker only ever observes (message, tag), never `key`.
+    let tag = mac::Hls1024MacTag(key, message);
+
+    let mut extended = message.to_vec();
+    extended.extend_from_slice(suffix);
+    let real_tag = mac::Hls1024MacTag(key, &extended);
+
+    let forged_tag = ForgeByStateReconstruction(&tag, suffix);
+
+    if forged_tag == real_tag {
+        println!("FAIL: length-extension forgery succeeded");
+    } else {
+        println!("PASS: length-extension forgery rejected");
+    }
 }
 
 fn main() {
@@ -291,25 +564,41 @@ fn main() {
     let mut message: Option<String> = None;
     let mut file_path: Option<String> = None;
     let mut selftest = false;
+    let mut bench_field = false;
+    let mut mine_bits: Option<u32> = None;
+    let mut verify_args: Option<(u64, u32)> = None;
 
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
-            "-m" | "--message" => {
-                if i + 1 < args.len() {
-                    message = Some(args[i + 1].clone());
-                    i += 1;
-                }
+            "-m" | "--message" if i + 1 < args.len() => {
+                message = Some(args[i + 1].clone());
+                i += 1;
             }
-            "-f" | "--file" => {
-                if i + 1 < args.len() {
-                    file_path = Some(args[i + 1].clone());
-                    i += 1;
-                }
+            "-f" | "--file" if i + 1 < args.len() => {
+                file_path = Some(args[i + 1].clone());
+                i += 1;
             }
             "--selftest" => {
                 selftest = true;
             }
+            "--bench-field" => {
+                bench_field = true;
+            }
+            "--mine" if i + 1 < args.len() => {
+                mine_bits = Some(
+                    u32::from_str_radix(args[i + 1].trim_start_matches("0x"), 16)
+                        .expect("--mine expects a hex compact target (e.g. 1d00ffff)"),
+                );
+                i += 1;
+            }
+            "--verify" if i + 2 < args.len() => {
+                let nonce: u64 = args[i + 1].parse().expect("--verify expects <nonce> <compact_bits>");
+                let bits = u32::from_str_radix(args[i + 2].trim_start_matches("0x"), 16)
+                    .expect("--verify expects <nonce> <compact_bits>");
+                verify_args = Some((nonce, bits));
+                i += 2;
+            }
             _ => {}
         }
         i += 1;
@@ -320,16 +609,105 @@ fn main() {
         return;
     }
 
-    let data: Vec<u8> = if let Some(path) = file_path {
-        fs::read(path).expect("Failed to read file")
+    if bench_field {
+        RunFieldBenchmark();
+        return;
+    }
+
+    if mine_bits.is_some() || verify_args.is_some() {
+        let data: Vec<u8> = if let Some(path) = file_path {
+            fs::read(path).expect("Failed to read file")
+        } else if let Some(msg) = message {
+            msg.into_bytes()
+        } else {
+            let mut buffer = Vec::new();
+            io::stdin().read_to_end(&mut buffer).unwrap();
+            buffer
+        };
+
+        if let Some(bits) = mine_bits {
+            let target = pow::compact_to_target(bits);
+            match pow::mine(&data, &target, 0..u64::MAX) {
+                Some((nonce, digest)) => {
+                    let reached = pow::target_to_compact(&BigUint::from_bytes_be(digest.as_bytes()));
+                    println!(
+                        "nonce={} digest={} compact={:08x}",
+                        nonce,
+                        digest.to_hex(),
+                        reached
+                    );
+                }
+                None => println!("no nonce found in range"),
+            }
+        }
+
+        if let Some((nonce, bits)) = verify_args {
+            let target = pow::compact_to_target(bits);
+            let ok = pow::verify_pow(&data, nonce, &target);
+            println!("{}", if ok { "PASS" } else { "FAIL" });
+        }
+
+        return;
+    }
+
+    let mut hasher = Hls1024::new();
+
+    if let Some(path) = file_path {
+        let mut f = fs::File::open(path).expect("Failed to open file");
+        let mut chunk = vec![0u8; 64 * 1024];
+        loop {
+            let n = f.read(&mut chunk).expect("Failed to read file");
+            if n == 0 {
+                break;
+            }
+            hasher.update(&chunk[..n]);
+        }
     } else if let Some(msg) = message {
-        msg.into_bytes()
+        hasher.update(msg.as_bytes());
     } else {
-        let mut buffer = Vec::new();
-        io::stdin().read_to_end(&mut buffer).unwrap();
-        buffer
-    };
+        let mut chunk = vec![0u8; 64 * 1024];
+        loop {
+            let n = io::stdin().read(&mut chunk).unwrap();
+            if n == 0 {
+                break;
+            }
+            hasher.update(&chunk[..n]);
+        }
+    }
 
-    let digest = Hls1024Hash(&data);
-    println!("{}", hex::encode(digest));
+    let digest = hasher.finalize();
+    println!("{}", digest.to_hex());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_deterministic() {
+        let msg = b"selftest";
+        assert_eq!(Hls1024Hash(msg), Hls1024Hash(msg));
+    }
+
+    #[test]
+    fn mac_resists_length_extension() {
+        let key = b"super-secret-mac-key";
+        let message = b"transfer:100:to:alice";
+        let suffix = b":admin:true";
+
+        let tag = mac::Hls1024MacTag(key, message);
+
+        let mut extended = message.to_vec();
+        extended.extend_from_slice(suffix);
+        let real_tag = mac::Hls1024MacTag(key, &extended);
+
+        let forged_tag = ForgeByStateReconstruction(&tag, suffix);
+        assert_ne!(forged_tag, real_tag, "length-extension forgery must not succeed");
+    }
+
+    #[test]
+    fn barrett_and_legacy_pipelines_agree() {
+        let msg = b"field-arithmetic-cross-check";
+        assert_eq!(Hls1024Hash(msg), LegacyHls1024Hash(msg));
+    }
 }
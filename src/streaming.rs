@@ -0,0 +1,79 @@
+// =========================================================
+//   STREAMING HASHER
+//   ---------------------------------------------------------
+//   Incremental Update/Finalize API over the HLS-1024 sponge,
+//   so large files/streams don't need to be buffered whole.
+// =========================================================
+
+use num_bigint_dig::BigUint;
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+use crate::digest::Digest1024;
+use crate::{
+    AbsorbMessageBlock, ExtractReader, FinalizeState, InitializeState, PerformRound,
+    SplitIntoBlocks, BlockBytes, OutputBitLength, Shake256,
+};
+
+/// Incremental HLS-1024 hasher, modeled on the block-at-a-time
+/// `Update`/`finalize` shape of typical SHA implementations.
+pub struct Hls1024 {
+    state: Vec<BigUint>,
+    buffer: Vec<u8>,
+}
+
+impl Hls1024 {
+    pub fn new() -> Self {
+        Hls1024 {
+            state: InitializeState(),
+            buffer: Vec::new(),
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        self.state = AbsorbMessageBlock(&self.state, block);
+        for _ in 0..crate::RoundCount {
+            self.state = PerformRound(&self.state);
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        while self.buffer.len() >= BlockBytes {
+            let block: Vec<u8> = self.buffer.drain(..BlockBytes).collect();
+            self.absorb_block(&block);
+        }
+    }
+
+    pub fn finalize(self) -> Digest1024 {
+        let mut reader = self.finalize_xof();
+        let mut out = [0u8; OutputBitLength / 8];
+        XofReader::read(&mut reader, &mut out);
+        Digest1024::from_array(out)
+    }
+}
+
+impl Default for Hls1024 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Update for Hls1024 {
+    fn update(&mut self, data: &[u8]) {
+        Hls1024::update(self, data)
+    }
+}
+
+impl ExtendableOutput for Hls1024 {
+    type Reader = <Shake256 as ExtendableOutput>::Reader;
+
+    fn finalize_xof(mut self) -> Self::Reader {
+        let residual = std::mem::take(&mut self.buffer);
+        for blk in SplitIntoBlocks(&residual) {
+            self.absorb_block(&blk);
+        }
+        self.state = FinalizeState(&self.state);
+        ExtractReader(&self.state)
+    }
+}